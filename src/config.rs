@@ -1,34 +1,235 @@
 use bdk::blockchain::any::{AnyBlockchain, AnyBlockchainConfig};
 use bdk::blockchain::electrum::ElectrumBlockchainConfig;
+use bdk::blockchain::esplora::{EsploraBlockchainConfig, EsploraError};
 use bdk::blockchain::rpc::{wallet_name_from_descriptor, Auth, RpcConfig};
-use bdk::blockchain::{Blockchain, ConfigurableBlockchain, ElectrumBlockchain, RpcBlockchain};
+use bdk::blockchain::{
+  Blockchain, ConfigurableBlockchain, ElectrumBlockchain, EsploraBlockchain, RpcBlockchain,
+};
 use bdk::core_rpc::Error as RpcError;
-use bdk::electrum_client::Error as ElectrumError;
+use bdk::electrum_client::{Error as ElectrumError, ElectrumApi, GetHistoryRes, ListUnspentRes};
 
 use bitcoin::network::constants::Network;
 use bitcoin::secp256k1::Secp256k1;
+use bitcoin::Script;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use crate::e::{ErrorKind, S5Error};
 
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Locally cached view of a set of watched scripts, refreshed with one batched
+/// Electrum request per method (`listunspent`, `get_history`) rather than one
+/// call per script.
+pub struct LocalCache {
+  pub utxos: HashMap<Script, Vec<ListUnspentRes>>,
+  pub history: HashMap<Script, Vec<GetHistoryRes>>,
+  pub tip_height: u32,
+}
+
+/// Tuning for how a Bitcoin Core RPC backend catches up to the chain tip.
+/// Without this, a freshly created RPC wallet rescans from genesis, which can
+/// take hours; setting `skip_blocks` to the wallet's birthday height instead
+/// syncs in seconds.
+///
+/// `bdk::blockchain::rpc::RpcConfig` in the bdk version this crate depends on
+/// only exposes `skip_blocks` for scan tuning — there is no poll-interval or
+/// batch-size knob to thread a value into, so this struct doesn't pretend to
+/// offer fields it can't honor. Revisit if/when bdk's `RpcConfig` grows one.
+#[derive(Clone, Debug)]
+pub struct RpcSyncParams {
+  /// Height below which `scantxoutset`-style scanning is skipped entirely.
+  pub skip_blocks: Option<u32>,
+}
+
+impl Default for RpcSyncParams {
+  fn default() -> Self {
+    RpcSyncParams { skip_blocks: None }
+  }
+}
+
 pub struct WalletConfig {
   pub deposit_desc: String,
   pub change_desc: String,
   pub network: Network,
   pub client: Option<AnyBlockchain>,
+  pub refresh_interval: Duration,
+  pub last_synced: Option<Instant>,
+  pub cache: Option<LocalCache>,
+  pub rpc_sync_params: RpcSyncParams,
+  /// SOCKS5 proxy address (e.g. a local Tor SOCKS port) used by the Electrum
+  /// and Esplora backends. `bitcoincore_rpc`/`bdk::blockchain::rpc::RpcConfig`
+  /// expose no proxy hook, so a `socks5` value is rejected outright for an RPC
+  /// node address rather than being silently dropped.
+  pub socks5: Option<String>,
 }
 
 pub const DEFAULT: &str = "default";
 pub const DEFAULT_TESTNET_NODE: &str = "ssl://electrum.blockstream.info:60002";
 pub const DEFAULT_MAINNET_NODE: &str = "ssl://electrum.blockstream.info:50002";
+pub const DEFAULT_SIGNET_NODE: &str = "ssl://electrum.blockstream.info:60602";
+
+/// Checks that `descriptor`'s extended key prefix is consistent with `network`,
+/// e.g. a `tprv`/`tpub` descriptor cannot be loaded as `Network::Bitcoin`.
+/// Signet and regtest descriptors use the same `tpub`/`tprv` prefix as testnet.
+fn validate_descriptor_network(descriptor: &str, network: Network) -> Result<(), S5Error> {
+  let is_mainnet_key = descriptor.contains("xpub") || descriptor.contains("xprv");
+  let is_testnet_key = descriptor.contains("tpub") || descriptor.contains("tprv");
+
+  let matches = match network {
+    Network::Bitcoin => is_mainnet_key,
+    Network::Testnet | Network::Signet | Network::Regtest => is_testnet_key,
+  };
+
+  if matches {
+    Ok(())
+  } else {
+    Err(S5Error::new(
+      ErrorKind::Internal,
+      &format!(
+        "Descriptor key prefix does not match requested network {:?}.",
+        network
+      ),
+    ))
+  }
+}
+
+/// Splits an `http(s)://host:port[?auth=user:pass|?cookie=path]` node address
+/// at the first `?` into the bare URL and the `Auth` Core RPC should use,
+/// supporting URL-encoded credentials and cookie-file authentication. Returns
+/// `ErrorKind::Internal` rather than panicking when nothing precedes the `?`,
+/// when the `cookie` path is empty, or when `auth` has no `:`. The scheme, host
+/// and port are not validated, and unrecognised query fields are ignored.
+fn parse_node_address(node_address: &str) -> Result<(String, Auth), S5Error> {
+  let (base, query) = match node_address.split_once('?') {
+    Some((base, query)) => (base, Some(query)),
+    None => (node_address, None),
+  };
+
+  if base.is_empty() {
+    return Err(S5Error::new(
+      ErrorKind::Internal,
+      "Malformed node address: missing host.",
+    ));
+  }
+
+  let query = match query {
+    Some(query) => query,
+    None => return Ok((base.to_string(), Auth::None)),
+  };
+
+  for field in query.split('&') {
+    if let Some(cookie_path) = field.strip_prefix("cookie=") {
+      if cookie_path.is_empty() {
+        return Err(S5Error::new(
+          ErrorKind::Internal,
+          "Malformed node address: `cookie` path is empty.",
+        ));
+      }
+      return Ok((
+        base.to_string(),
+        Auth::Cookie {
+          file: PathBuf::from(percent_decode(cookie_path)),
+        },
+      ));
+    }
+
+    if let Some(user_pass) = field.strip_prefix("auth=") {
+      if user_pass.is_empty() {
+        return Ok((base.to_string(), Auth::None));
+      }
+      let (username, password) = match user_pass.split_once(':') {
+        Some(parts) => parts,
+        None => {
+          return Err(S5Error::new(
+            ErrorKind::Internal,
+            "Malformed node address: `auth` must be `user:pass`.",
+          ))
+        }
+      };
+      return Ok((
+        base.to_string(),
+        Auth::UserPass {
+          username: percent_decode(username),
+          password: percent_decode(password),
+        },
+      ));
+    }
+  }
+
+  Ok((base.to_string(), Auth::None))
+}
+
+/// Decodes `%XX` percent-escapes so credentials containing reserved
+/// characters (e.g. a password with a colon or `%`) survive the round trip.
+fn percent_decode(value: &str) -> String {
+  let bytes = value.as_bytes();
+  let mut out = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    let escaped_byte = if bytes[i] == b'%' {
+      bytes.get(i + 1..i + 3).and_then(|hex| {
+        std::str::from_utf8(hex)
+          .ok()
+          .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+      })
+    } else {
+      None
+    };
+
+    match escaped_byte {
+      Some(byte) => {
+        out.push(byte);
+        i += 3;
+      }
+      None => {
+        out.push(bytes[i]);
+        i += 1;
+      }
+    }
+  }
+  String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Whether `refresh_interval` has elapsed since `last_synced`, i.e. whether
+/// [`WalletConfig::sync`] should hit the network rather than serve the cache.
+fn is_sync_stale(last_synced: Option<Instant>, refresh_interval: Duration) -> bool {
+  match last_synced {
+    Some(last) => last.elapsed() >= refresh_interval,
+    None => true,
+  }
+}
+
+/// Pairs each of `scripts` with the batch-call result at the same index.
+/// Electrum's batch RPCs return results in request order, so `batch` is
+/// assumed to be the same length as `scripts` and in the same order.
+fn zip_scripts<T>(scripts: &[Script], batch: Vec<T>) -> HashMap<Script, T> {
+  scripts
+    .iter()
+    .cloned()
+    .zip(batch.into_iter())
+    .collect()
+}
+
+/// Picks the chain tip to cache: a freshly popped header notification wins,
+/// otherwise the previously cached tip, otherwise `None` to signal that the
+/// caller should fall back to a fresh header subscription.
+fn resolve_tip_height(popped: Option<u32>, cached: Option<u32>) -> Option<u32> {
+  popped.or(cached)
+}
 
 impl WalletConfig {
+  /// Infers `Network` from the descriptor's key prefix: `xpub`/`xprv` is taken
+  /// to mean mainnet, anything else testnet. This cannot distinguish signet or
+  /// regtest from testnet; use [`WalletConfig::new_with_network`] when that
+  /// matters.
   pub fn new(
     descriptor: &str,
     node_address: &str,
     socks5: Option<String>,
   ) -> Result<Self, S5Error> {
-    let deposit_desc: &str = &descriptor.replace("/*", "/0/*");
-    let change_desc: &str = &descriptor.replace("/*", "/1/*");
     let network = if <&str>::clone(&descriptor).contains("xpub")
       || <&str>::clone(&descriptor).contains("xprv")
     {
@@ -37,10 +238,71 @@ impl WalletConfig {
       Network::Testnet
     };
 
+    WalletConfig::from_network(
+      descriptor,
+      node_address,
+      socks5,
+      network,
+      RpcSyncParams::default(),
+    )
+  }
+
+  /// Builds a `WalletConfig` for an explicitly chosen `network`, rather than
+  /// guessing it from the descriptor's key prefix. The descriptor's key
+  /// prefixes are validated against `network` so a testnet/signet/regtest
+  /// descriptor can't silently be loaded as mainnet or vice versa.
+  pub fn new_with_network(
+    descriptor: &str,
+    node_address: &str,
+    socks5: Option<String>,
+    network: Network,
+  ) -> Result<Self, S5Error> {
+    validate_descriptor_network(descriptor, network)?;
+    WalletConfig::from_network(
+      descriptor,
+      node_address,
+      socks5,
+      network,
+      RpcSyncParams::default(),
+    )
+  }
+
+  /// As [`WalletConfig::new_with_network`], but lets an RPC (Bitcoin Core)
+  /// backend be tuned with [`RpcSyncParams`] instead of always rescanning
+  /// from genesis. Ignored for Electrum/Esplora backends.
+  pub fn new_with_rpc_sync_params(
+    descriptor: &str,
+    node_address: &str,
+    socks5: Option<String>,
+    network: Network,
+    rpc_sync_params: RpcSyncParams,
+  ) -> Result<Self, S5Error> {
+    validate_descriptor_network(descriptor, network)?;
+    WalletConfig::from_network(descriptor, node_address, socks5, network, rpc_sync_params)
+  }
+
+  fn from_network(
+    descriptor: &str,
+    node_address: &str,
+    socks5: Option<String>,
+    network: Network,
+    rpc_sync_params: RpcSyncParams,
+  ) -> Result<Self, S5Error> {
+    let deposit_desc: &str = &descriptor.replace("/*", "/0/*");
+    let change_desc: &str = &descriptor.replace("/*", "/1/*");
+    let socks5_proxy = socks5.clone();
+
     let node_address = if node_address.contains(DEFAULT) {
       match network {
         Network::Bitcoin => DEFAULT_MAINNET_NODE,
-        _ => DEFAULT_TESTNET_NODE,
+        Network::Signet => DEFAULT_SIGNET_NODE,
+        Network::Testnet => DEFAULT_TESTNET_NODE,
+        Network::Regtest => {
+          return Err(S5Error::new(
+            ErrorKind::Internal,
+            "No default node for Regtest: regtest is local-only, pass an explicit node_address.",
+          ))
+        }
       }
     } else {
       node_address
@@ -74,17 +336,45 @@ impl WalletConfig {
         change_desc: change_desc.to_string(),
         network,
         client: Some(client),
+        refresh_interval: DEFAULT_REFRESH_INTERVAL,
+        last_synced: None,
+        cache: None,
+        rpc_sync_params: rpc_sync_params.clone(),
+        socks5: socks5_proxy.clone(),
       })
-    } else if node_address.contains("http") {
-      let parts: Vec<&str> = node_address.split("?auth=").collect();
-      let auth = if parts[1].is_empty() {
-        Auth::None
-      } else {
-        Auth::UserPass {
-          username: parts[1].split(':').collect::<Vec<&str>>()[0].to_string(),
-          password: parts[1].split(':').collect::<Vec<&str>>()[1].to_string(),
-        }
+    } else if node_address.contains("esplora://") || node_address.contains("/api") {
+      let base_url = node_address.replace("esplora://", "https://");
+      let config = EsploraBlockchainConfig {
+        base_url,
+        proxy: socks5,
+        concurrency: None,
+        stop_gap: 1000,
+        timeout: Some(5),
       };
+      let client = match create_blockchain_client(AnyBlockchainConfig::Esplora(config)) {
+        Ok(client) => client,
+        Err(e) => return Err(S5Error::new(ErrorKind::Internal, &e.message)),
+      };
+
+      Ok(WalletConfig {
+        deposit_desc: deposit_desc.to_string(),
+        change_desc: change_desc.to_string(),
+        network,
+        client: Some(client),
+        refresh_interval: DEFAULT_REFRESH_INTERVAL,
+        last_synced: None,
+        cache: None,
+        rpc_sync_params: rpc_sync_params.clone(),
+        socks5: socks5_proxy.clone(),
+      })
+    } else if node_address.contains("http") {
+      if socks5.is_some() {
+        return Err(S5Error::new(
+          ErrorKind::Internal,
+          "SOCKS5 proxying is not supported for the Bitcoin Core RPC backend; route it via a system-level proxy (e.g. torsocks) instead.",
+        ));
+      }
+      let (rpc_url, auth) = parse_node_address(node_address)?;
       let wallet_name = match wallet_name_from_descriptor(
         descriptor,
         Some(change_desc),
@@ -95,11 +385,11 @@ impl WalletConfig {
         Err(e) => return Err(S5Error::new(ErrorKind::Internal, &e.to_string())),
       };
       let config = RpcConfig {
-        url: parts[0].to_string(),
+        url: rpc_url,
         auth,
         network,
         wallet_name,
-        skip_blocks: None,
+        skip_blocks: rpc_sync_params.skip_blocks,
       };
       let client = match create_blockchain_client(AnyBlockchainConfig::Rpc(config)) {
         Ok(client) => client,
@@ -111,6 +401,11 @@ impl WalletConfig {
         change_desc: change_desc.to_string(),
         network,
         client: Some(client),
+        refresh_interval: DEFAULT_REFRESH_INTERVAL,
+        last_synced: None,
+        cache: None,
+        rpc_sync_params: rpc_sync_params.clone(),
+        socks5: socks5_proxy.clone(),
       })
     } else {
       Err(S5Error::new(ErrorKind::Internal, "Invalid Node Address."))
@@ -118,8 +413,6 @@ impl WalletConfig {
   }
 
   pub fn new_offline(descriptor: &str) -> Result<Self, S5Error> {
-    let deposit_desc: &str = &descriptor.replace("/*", "/0/*");
-    let change_desc: &str = &descriptor.replace("/*", "/1/*");
     let network = if <&str>::clone(&descriptor).contains("xpub")
       || <&str>::clone(&descriptor).contains("xprv")
     {
@@ -128,13 +421,100 @@ impl WalletConfig {
       Network::Testnet
     };
 
+    WalletConfig::new_offline_with_network(descriptor, network)
+  }
+
+  /// As [`WalletConfig::new_offline`], but for an explicitly chosen `network`
+  /// instead of one inferred from the descriptor's key prefix.
+  pub fn new_offline_with_network(descriptor: &str, network: Network) -> Result<Self, S5Error> {
+    validate_descriptor_network(descriptor, network)?;
+
+    let deposit_desc: &str = &descriptor.replace("/*", "/0/*");
+    let change_desc: &str = &descriptor.replace("/*", "/1/*");
+
     Ok(WalletConfig {
       deposit_desc: deposit_desc.to_string(),
       change_desc: change_desc.to_string(),
       network,
       client: None,
+      refresh_interval: DEFAULT_REFRESH_INTERVAL,
+      last_synced: None,
+      cache: None,
+      rpc_sync_params: RpcSyncParams::default(),
+      socks5: None,
     })
   }
+
+  /// Refreshes the local cache for `scripts` using one batched Electrum request
+  /// for unspent outputs and one for history, unless `refresh_interval` has not
+  /// yet elapsed since the last sync.
+  /// Balance/history/UTXO queries should read from the cache rather than calling
+  /// this on every invocation.
+  pub fn sync(&mut self, scripts: &[Script]) -> Result<(), S5Error> {
+    if !is_sync_stale(self.last_synced, self.refresh_interval) {
+      return Ok(());
+    }
+
+    let electrum = match &self.client {
+      Some(AnyBlockchain::Electrum(electrum)) => electrum,
+      _ => {
+        return Err(S5Error::new(
+          ErrorKind::Internal,
+          "Local cache sync requires an Electrum client.",
+        ))
+      }
+    };
+
+    let script_refs: Vec<&Script> = scripts.iter().collect();
+    let utxo_batch = match electrum.batch_script_list_unspent(script_refs.clone()) {
+      Ok(res) => res,
+      Err(e) => return Err(S5Error::new(ErrorKind::Network, &e.to_string())),
+    };
+    let history_batch = match electrum.batch_script_get_history(script_refs) {
+      Ok(res) => res,
+      Err(e) => return Err(S5Error::new(ErrorKind::Network, &e.to_string())),
+    };
+
+    let utxos = zip_scripts(scripts, utxo_batch);
+    let history = zip_scripts(scripts, history_batch);
+
+    // Drain the full queue of header notifications instead of polling
+    // `get_height` or stopping at the first one — more than one block can
+    // land between syncs, and popping only once would leave the cached tip
+    // permanently behind. Falls back to the previous tip, or a fresh
+    // subscription on the very first sync.
+    let cached_tip = self.cache.as_ref().map(|cache| cache.tip_height);
+    let mut popped_tip = None;
+    loop {
+      match electrum.block_headers_pop() {
+        Ok(Some(header)) => popped_tip = Some(header.height as u32),
+        Ok(None) => break,
+        Err(e) => return Err(S5Error::new(ErrorKind::Network, &e.to_string())),
+      }
+    }
+    let tip_height = match resolve_tip_height(popped_tip, cached_tip) {
+      Some(tip_height) => tip_height,
+      None => match electrum.block_headers_subscribe() {
+        Ok(header) => header.height as u32,
+        Err(e) => return Err(S5Error::new(ErrorKind::Network, &e.to_string())),
+      },
+    };
+
+    self.cache = Some(LocalCache {
+      utxos,
+      history,
+      tip_height,
+    });
+    self.last_synced = Some(Instant::now());
+    Ok(())
+  }
+
+  /// Total value of all cached UTXOs, in satoshis. Returns `None` if `sync` has
+  /// not been called yet.
+  pub fn cached_balance(&self) -> Option<u64> {
+    let cache = self.cache.as_ref()?;
+    Some(cache.utxos.values().flatten().map(|utxo| utxo.value).sum())
+  }
 }
 
 pub fn create_blockchain_client(config: AnyBlockchainConfig) -> Result<AnyBlockchain, S5Error> {
@@ -170,6 +550,27 @@ pub fn create_blockchain_client(config: AnyBlockchainConfig) -> Result<AnyBlockc
       };
       Ok(AnyBlockchain::Rpc(client))
     }
+    AnyBlockchainConfig::Esplora(conf) => {
+      let client = match EsploraBlockchain::from_config(&conf) {
+        Ok(result) => result,
+        Err(bdk_error) => match bdk_error {
+          bdk::Error::Esplora(esplora_error) => match *esplora_error {
+            EsploraError::Io(io_error) => {
+              return Err(S5Error::new(ErrorKind::Network, &io_error.to_string()))
+            }
+            EsploraError::HttpResponse(status) => {
+              return Err(S5Error::new(
+                ErrorKind::Network,
+                &format!("Esplora server returned HTTP {}", status),
+              ))
+            }
+            e_error => return Err(S5Error::new(ErrorKind::Internal, &e_error.to_string())),
+          },
+          e_error => return Err(S5Error::new(ErrorKind::Internal, &e_error.to_string())),
+        },
+      };
+      Ok(AnyBlockchain::Esplora(client))
+    }
   }
 }
 
@@ -187,17 +588,9 @@ pub fn _check_client(network: Network, node_address: &str) -> Result<bool, S5Err
       Err(e) => return Err(S5Error::new(ErrorKind::Internal, &e.message)),
     }
   } else if node_address.contains("http") {
-    let parts: Vec<&str> = node_address.split("?auth=").collect();
-    let auth = if parts[1].is_empty() {
-      Auth::None
-    } else {
-      Auth::UserPass {
-        username: parts[1].split(':').collect::<Vec<&str>>()[0].to_string(),
-        password: parts[1].split(':').collect::<Vec<&str>>()[1].to_string(),
-      }
-    };
+    let (rpc_url, auth) = parse_node_address(node_address)?;
     let config = RpcConfig {
-      url: parts[0].to_string(),
+      url: rpc_url,
       auth,
       network,
       wallet_name: "ping".to_string(),
@@ -224,6 +617,42 @@ mod tests {
   use crate::config::WalletConfig;
   use bdk::blockchain::Blockchain;
   use bitcoin::network::constants::Network;
+
+  #[test]
+  fn test_is_sync_stale() {
+    assert_eq!(is_sync_stale(None, Duration::from_secs(60)), true);
+    assert_eq!(
+      is_sync_stale(Some(Instant::now()), Duration::from_secs(60)),
+      false
+    );
+    let long_ago = Instant::now() - Duration::from_secs(120);
+    assert_eq!(is_sync_stale(Some(long_ago), Duration::from_secs(60)), true);
+  }
+
+  #[test]
+  fn test_zip_scripts_preserves_order() {
+    let scripts = vec![
+      Script::from(vec![0x00]),
+      Script::from(vec![0x01]),
+      Script::from(vec![0x02]),
+    ];
+    let batch = vec![10u32, 11u32, 12u32];
+
+    let zipped = zip_scripts(&scripts, batch);
+
+    assert_eq!(zipped.len(), 3);
+    assert_eq!(zipped.get(&scripts[0]), Some(&10));
+    assert_eq!(zipped.get(&scripts[1]), Some(&11));
+    assert_eq!(zipped.get(&scripts[2]), Some(&12));
+  }
+
+  #[test]
+  fn test_resolve_tip_height() {
+    assert_eq!(resolve_tip_height(Some(100), Some(90)), Some(100));
+    assert_eq!(resolve_tip_height(None, Some(90)), Some(90));
+    assert_eq!(resolve_tip_height(None, None), None);
+  }
+
   #[test]
   fn test_default_electrum_config() {
     let xkey = "[db7d25b5/84'/1'/6']tpubDCCh4SuT3pSAQ1qAN86qKEzsLoBeiugoGGQeibmieRUKv8z6fCTTmEXsb9yeueBkUWjGVzJr91bCzeCNShorbBqjZV4WRGjz3CrJsCboXUe";
@@ -245,6 +674,28 @@ mod tests {
     assert_eq!(config.network, network);
   }
 
+  #[test]
+  fn test_default_esplora_config() {
+    let xkey = "[db7d25b5/84'/1'/6']tpubDCCh4SuT3pSAQ1qAN86qKEzsLoBeiugoGGQeibmieRUKv8z6fCTTmEXsb9yeueBkUWjGVzJr91bCzeCNShorbBqjZV4WRGjz3CrJsCboXUe";
+    let descriptor = format!("wpkh({}/*)", xkey);
+    let node_address = "https://blockstream.info/testnet/api";
+
+    let config = WalletConfig::new(&descriptor, node_address, None).unwrap();
+    match config.client.unwrap() {
+      AnyBlockchain::Esplora(client) => {
+        let height = client.get_height().unwrap();
+        println!("{:#?}", height);
+        assert_eq!((height > 2097921), true);
+      }
+      _ => println!("Should not reach."),
+    };
+
+    let change_desc = format!("wpkh({}/1/*)", xkey);
+    let network = Network::Testnet;
+    assert_eq!(config.change_desc, change_desc);
+    assert_eq!(config.network, network);
+  }
+
   #[test]
   #[ignore]
   fn test_local_rpc_config() {
@@ -269,6 +720,25 @@ mod tests {
     // println!("Connect a local node and then remove ignore macro.")
   }
 
+  #[test]
+  fn test_malformed_http_address_does_not_panic() {
+    let xkey = "[db7d25b5/84'/1'/6']tpubDCCh4SuT3pSAQ1qAN86qKEzsLoBeiugoGGQeibmieRUKv8z6fCTTmEXsb9yeueBkUWjGVzJr91bCzeCNShorbBqjZV4WRGjz3CrJsCboXUe";
+    let descriptor = format!("wpkh({}/*)", xkey);
+    let node_address = "http://172.18.0.2:18332?auth=satsbank";
+    let config_error = WalletConfig::new(&descriptor, node_address, None)
+      .err()
+      .unwrap();
+    println!("{:#?}", config_error);
+  }
+
+  #[test]
+  fn test_percent_decode_does_not_panic_on_multibyte_char_after_percent() {
+    // A `%` immediately followed by a multi-byte UTF-8 character used to
+    // panic on a non-char-boundary slice instead of falling through as an
+    // invalid escape.
+    assert_eq!(percent_decode("pass%€word"), "pass%€word");
+  }
+
   #[test]
 
   fn test_config_errors() {
@@ -279,4 +749,30 @@ mod tests {
       .unwrap();
     println!("{:#?}", config_error);
   }
+
+  #[test]
+  fn test_regtest_requires_explicit_node_address() {
+    let xkey = "[db7d25b5/84'/1'/6']tpubDCCh4SuT3pSAQ1qAN86qKEzsLoBeiugoGGQeibmieRUKv8z6fCTTmEXsb9yeueBkUWjGVzJr91bCzeCNShorbBqjZV4WRGjz3CrJsCboXUe";
+    let descriptor = format!("wpkh({}/*)", xkey);
+    let config_error =
+      WalletConfig::new_with_network(&descriptor, DEFAULT, None, Network::Regtest)
+        .err()
+        .unwrap();
+    println!("{:#?}", config_error);
+  }
+
+  #[test]
+  fn test_socks5_rejected_for_rpc_backend() {
+    let xkey = "[db7d25b5/84'/1'/6']tpubDCCh4SuT3pSAQ1qAN86qKEzsLoBeiugoGGQeibmieRUKv8z6fCTTmEXsb9yeueBkUWjGVzJr91bCzeCNShorbBqjZV4WRGjz3CrJsCboXUe";
+    let descriptor = format!("wpkh({}/*)", xkey);
+    let node_address = "http://172.18.0.2:18332?auth=satsbank:typercuz";
+    let config_error = WalletConfig::new(
+      &descriptor,
+      node_address,
+      Some("127.0.0.1:9050".to_string()),
+    )
+    .err()
+    .unwrap();
+    println!("{:#?}", config_error);
+  }
 }